@@ -15,15 +15,37 @@ use alloc::vec;
 use alloc::vec::Vec;
 use stylus_sdk::{
     alloy_primitives::{Address, B256, U256},
-    crypto,
+    alloy_sol_types::sol,
+    block,
+    call::RawCall,
+    contract, crypto,
     deploy::RawDeploy,
-    msg,
+    evm, msg,
     prelude::*,
 };
 
+sol! {
+    /// Emitted for every instance deployed by the factory.
+    event AuctionCreated(
+        uint256 indexed id,
+        address indexed creator,
+        address instance,
+        address indexed nftContract,
+        uint256 tokenId
+    );
+    /// Emitted when an admin pauses new deployments.
+    event FactoryPaused(address by);
+    /// Emitted when an admin resumes new deployments.
+    event FactoryUnpaused(address by);
+}
+
 // Import the compiled sealed-bid auction WASM bytecode at compile time
 static SEALED_BID_AUCTION_WASM: &[u8] = include_bytes!("sealed_bid_auction.wasm");
 
+// Upper bound on edition-series size so `create_auction_series` gas stays
+// predictable and bounded.
+const MAX_SERIES_BATCH: usize = 50;
+
 sol_storage! {
     #[entrypoint]
     pub struct SealedBidAuctionFactory {
@@ -36,9 +58,31 @@ sol_storage! {
         // optional: id => creator
         mapping(uint256 => address) creators;
 
+        // id => whether a buy-it-now buyout price was offered
+        mapping(uint256 => bool) buyout_enabled;
+
+        // id => optional oracle key authorized to attest settlement (zero = none)
+        mapping(uint256 => address) oracle_of;
+
+        // id => first id of its edition series (links batch children together)
+        mapping(uint256 => uint256) series_of;
+
+        // reverse index: nft contract => ids deployed against it
+        mapping(address => uint256[]) nft_to_auctions;
+
         // factory owner (admin)
         address owner;
 
+        // registered cross-chain verifier; recovered signer of attested messages
+        // must equal this address (zero = cross-chain entry point disabled)
+        address verifier;
+
+        // id => originating chain id for cross-chain deployments
+        mapping(uint256 => uint32) origin_chain;
+
+        // replay protection: nonces already consumed by attested messages
+        mapping(bytes32 => bool) consumed_nonces;
+
         // optional safety: allow pausing new deployments
         bool paused;
     }
@@ -61,6 +105,7 @@ impl SealedBidAuctionFactory {
     pub fn pause(&mut self) -> Result<(), Vec<u8>> {
         self.only_owner()?;
         self.paused.set(true);
+        evm::log(FactoryPaused { by: msg::sender() });
         Ok(())
     }
 
@@ -68,6 +113,7 @@ impl SealedBidAuctionFactory {
     pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
         self.only_owner()?;
         self.paused.set(false);
+        evm::log(FactoryUnpaused { by: msg::sender() });
         Ok(())
     }
 
@@ -81,6 +127,15 @@ impl SealedBidAuctionFactory {
     /// - `commit_duration`: seconds for commit phase
     /// - `reveal_duration`: seconds for reveal phase (must be > 0)
     /// - `min_deposit`: wei required to commit (anti-spam / griefing bound)
+    /// - `buyout_price`: buy-it-now price (0 = disabled). The factory validates
+    ///   it (`0` or `>= reserve_price`), binds it into the deterministic salt, and
+    ///   records the flag in `buyout_enabled`. `RawDeploy` forwards no constructor
+    ///   calldata, so the instant-sale path itself lives on the instance and is out
+    ///   of this factory's scope.
+    /// - `oracle`: optional oracle key (0 = none). Bound into the deterministic
+    ///   salt and recorded in `oracle_of`, fixed at deployment. `RawDeploy` forwards
+    ///   no constructor calldata, so the attested-settlement path that consumes this
+    ///   key lives on the instance and is out of this factory's scope.
     pub fn create_auction(
         &mut self,
         nft_contract: Address,
@@ -89,61 +144,162 @@ impl SealedBidAuctionFactory {
         commit_duration: U256,
         reveal_duration: U256,
         min_deposit: U256,
+        buyout_price: U256,
+        oracle: Address,
     ) -> Result<Address, Vec<u8>> {
-        if self.paused.get() {
-            return Err("Factory is paused".as_bytes().to_vec());
+        let (_, deployed) = self.deploy_auction(
+            &RawDeployer,
+            nft_contract,
+            token_id,
+            reserve_price,
+            commit_duration,
+            reveal_duration,
+            min_deposit,
+            buyout_price,
+            oracle,
+        )?;
+        Ok(deployed)
+    }
+
+    /// Deploy a whole edition series of sealed-bid auctions in one transaction.
+    ///
+    /// Mirrors master-edition minting: one call spawns a numbered copy per token
+    /// id, each getting its own consecutive `auction_count` id, and every child is
+    /// linked back to the first id of the batch via `series_of` so a frontend can
+    /// group the edition run.
+    ///
+    /// Buyout is not offered on series deployments; use `create_auction` for that.
+    pub fn create_auction_series(
+        &mut self,
+        nft_contract: Address,
+        token_ids: Vec<U256>,
+        reserve_price: U256,
+        commit_duration: U256,
+        reveal_duration: U256,
+        min_deposit: U256,
+    ) -> Result<Vec<Address>, Vec<u8>> {
+        if token_ids.is_empty() {
+            return Err("Empty series".as_bytes().to_vec());
+        }
+        if token_ids.len() > MAX_SERIES_BATCH {
+            return Err("Series too large".as_bytes().to_vec());
         }
 
-        if nft_contract == Address::ZERO {
-            return Err("Invalid NFT contract".as_bytes().to_vec());
+        let mut deployed = Vec::with_capacity(token_ids.len());
+        let mut series_root = U256::ZERO;
+        for (i, token_id) in token_ids.into_iter().enumerate() {
+            let (id, addr) = self.deploy_auction(
+                &RawDeployer,
+                nft_contract,
+                token_id,
+                reserve_price,
+                commit_duration,
+                reveal_duration,
+                min_deposit,
+                U256::ZERO,
+                Address::ZERO,
+            )?;
+            if i == 0 {
+                series_root = id;
+            }
+            self.series_of.setter(id).set(series_root);
+            deployed.push(addr);
         }
-        if reveal_duration == U256::ZERO {
-            return Err("Reveal duration must be > 0".as_bytes().to_vec());
+
+        Ok(deployed)
+    }
+
+    /// Admin: register the cross-chain verifier key whose signatures authorize
+    /// `create_auction_from_message`. Setting it to zero disables that path.
+    pub fn set_verifier(&mut self, verifier: Address) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.verifier.set(verifier);
+        Ok(())
+    }
+
+    /// Registered cross-chain verifier (zero if unset)
+    pub fn get_verifier(&self) -> Address {
+        self.verifier.get()
+    }
+
+    /// Deploy an auction in response to a signed cross-chain message.
+    ///
+    /// Follows the guardian-attestation model: `attestation` must be a 65-byte
+    /// `(r, s, v)` signature over `keccak(address(this) || chain_id || payload)`
+    /// — domain-separated so a message can't be replayed against another factory
+    /// or chain — whose recovered signer equals the registered [`set_verifier`]
+    /// key. The payload carries the same
+    /// parameters as [`create_auction`], plus the originating chain id and a
+    /// replay-protection nonce, laid out as consecutive 32-byte words:
+    /// `nft_contract, token_id, reserve_price, commit_duration, reveal_duration,
+    /// min_deposit, buyout_price, oracle, origin_chain, nonce`.
+    pub fn create_auction_from_message(
+        &mut self,
+        payload: Vec<u8>,
+        attestation: Vec<u8>,
+    ) -> Result<Address, Vec<u8>> {
+        let verifier = self.verifier.get();
+        if verifier == Address::ZERO {
+            return Err("Verifier unset".as_bytes().to_vec());
         }
-        if commit_duration == U256::ZERO {
-            return Err("Commit duration must be > 0".as_bytes().to_vec());
+        if payload.len() < 10 * 32 {
+            return Err("Malformed payload".as_bytes().to_vec());
         }
-        if min_deposit == U256::ZERO {
-            return Err("Min deposit must be > 0".as_bytes().to_vec());
+
+        // Domain-separate the signed digest with this contract's address and the
+        // local chain id so a message signed for one deployment can't be replayed
+        // against another factory instance or a different chain.
+        let mut preimage = Vec::with_capacity(20 + 8 + payload.len());
+        preimage.extend_from_slice(contract::address().as_slice());
+        preimage.extend_from_slice(&block::chainid().to_be_bytes());
+        preimage.extend_from_slice(&payload);
+        let digest = B256::from_slice(&crypto::keccak(&preimage)[0..32]);
+        let signer = recover_signer(digest, &attestation)?;
+        if signer != verifier {
+            return Err("Bad attestation".as_bytes().to_vec());
         }
 
-        let next_id = self.auction_count.get() + U256::from(1u8);
-        let creator = msg::sender();
+        // Nonce is the final word; reject replays before touching any state.
+        let nonce = B256::from_slice(&payload[9 * 32..10 * 32]);
+        if self.consumed_nonces.get(nonce) {
+            return Err("Nonce already used".as_bytes().to_vec());
+        }
+        self.consumed_nonces.setter(nonce).set(true);
 
-        // Deterministic salt: binds instance to creator + asset + timing + id.
-        // Feel free to tweak the preimage to match your needs.
-        let mut salt_preimage = Vec::new();
-        salt_preimage.extend_from_slice(&next_id.as_le_bytes());
-        salt_preimage.extend_from_slice(creator.as_slice());
-        salt_preimage.extend_from_slice(nft_contract.as_slice());
-        salt_preimage.extend_from_slice(&token_id.as_le_bytes());
-        salt_preimage.extend_from_slice(&reserve_price.as_le_bytes());
-        salt_preimage.extend_from_slice(&commit_duration.as_le_bytes());
-        salt_preimage.extend_from_slice(&reveal_duration.as_le_bytes());
-        salt_preimage.extend_from_slice(&min_deposit.as_le_bytes());
-
-        let salt = B256::from_slice(&crypto::keccak(salt_preimage)[0..32]);
-
-        // Deploy instance using embedded bytecode and CREATE2
-        let deployed = unsafe {
-            RawDeploy::new()
-                .salt(salt)
-                .deploy(SEALED_BID_AUCTION_WASM, U256::ZERO)
-                .map_err(|e| {
-                    let mut err = "Deployment failed: ".as_bytes().to_vec();
-                    err.extend_from_slice(&e);
-                    err
-                })?
-        };
+        let nft_contract = word_to_address(&payload[0..32]);
+        let token_id = word_to_u256(&payload[32..64]);
+        let reserve_price = word_to_u256(&payload[64..96]);
+        let commit_duration = word_to_u256(&payload[96..128]);
+        let reveal_duration = word_to_u256(&payload[128..160]);
+        let min_deposit = word_to_u256(&payload[160..192]);
+        let buyout_price = word_to_u256(&payload[192..224]);
+        let oracle = word_to_address(&payload[224..256]);
+        let origin = word_to_u256(&payload[256..288]);
+        if origin > U256::from(u32::MAX) {
+            return Err("Origin chain out of range".as_bytes().to_vec());
+        }
 
-        // Book-keeping
-        self.auctions.setter(next_id).set(deployed);
-        self.creators.setter(next_id).set(creator);
-        self.auction_count.set(next_id);
+        let (id, deployed) = self.deploy_auction(
+            &RawDeployer,
+            nft_contract,
+            token_id,
+            reserve_price,
+            commit_duration,
+            reveal_duration,
+            min_deposit,
+            buyout_price,
+            oracle,
+        )?;
+        self.origin_chain.setter(id).set(origin.saturating_to::<u32>());
 
         Ok(deployed)
     }
 
+    /// Originating chain id for a cross-chain deployment (0 if locally created)
+    pub fn get_origin_chain(&self, id: U256) -> u32 {
+        self.origin_chain.get(id)
+    }
+
     /// Get auction address by id
     pub fn get_auction(&self, id: U256) -> Address {
         self.auctions.get(id)
@@ -154,6 +310,52 @@ impl SealedBidAuctionFactory {
         self.creators.get(id)
     }
 
+    /// Whether the auction was created with a buy-it-now buyout price
+    pub fn is_buyout_enabled(&self, id: U256) -> bool {
+        self.buyout_enabled.get(id)
+    }
+
+    /// Oracle key authorized to attest settlement for `id` (zero if none)
+    pub fn get_oracle(&self, id: U256) -> Address {
+        self.oracle_of.get(id)
+    }
+
+    /// First id of the edition series `id` belongs to (0 if not part of a series)
+    pub fn get_series_of(&self, id: U256) -> U256 {
+        self.series_of.get(id)
+    }
+
+    /// All instance addresses deployed against a given NFT contract.
+    pub fn get_auctions_by_nft(&self, nft_contract: Address) -> Vec<Address> {
+        let ids = self.nft_to_auctions.getter(nft_contract);
+        let mut out = Vec::with_capacity(ids.len());
+        for i in 0..ids.len() {
+            let id = ids.get(i).unwrap_or(U256::ZERO);
+            out.push(self.auctions.get(id));
+        }
+        out
+    }
+
+    /// Paginated enumeration of the registry by id, returning up to `limit`
+    /// instance addresses starting at the 1-based id `offset + 1`.
+    pub fn get_auctions(&self, offset: U256, limit: U256) -> Vec<Address> {
+        let count = self.auction_count.get();
+        let mut out = Vec::new();
+        // Ids are 1-based; an offset at the very top of the range has nothing
+        // beyond it, so return empty rather than overflowing.
+        let mut id = match offset.checked_add(U256::from(1u8)) {
+            Some(id) => id,
+            None => return out,
+        };
+        let mut remaining = limit;
+        while id <= count && remaining > U256::ZERO {
+            out.push(self.auctions.get(id));
+            id += U256::from(1u8);
+            remaining -= U256::from(1u8);
+        }
+        out
+    }
+
     /// Count
     pub fn get_auction_count(&self) -> U256 {
         self.auction_count.get()
@@ -175,6 +377,92 @@ impl SealedBidAuctionFactory {
     }
 }
 
+/// Abstraction over instance deployment. The production implementation wraps
+/// `RawDeploy`; a mock can stand in under `cfg(test)` so the factory's salt,
+/// validation, and registry logic can be exercised without a live Stylus VM.
+pub trait Deployer {
+    fn deploy(&self, salt: B256, code: &[u8], value: U256) -> Result<Address, Vec<u8>>;
+}
+
+/// Production deployer: CREATE2 via `RawDeploy` with the embedded bytecode.
+struct RawDeployer;
+
+impl Deployer for RawDeployer {
+    fn deploy(&self, salt: B256, code: &[u8], value: U256) -> Result<Address, Vec<u8>> {
+        unsafe {
+            RawDeploy::new().salt(salt).deploy(code, value).map_err(|e| {
+                let mut err = "Deployment failed: ".as_bytes().to_vec();
+                err.extend_from_slice(&e);
+                err
+            })
+        }
+    }
+}
+
+/// Deterministic CREATE2 salt binding an instance to its creator, asset, timing,
+/// id, and optional buyout/oracle keys. Pure over its inputs so salt determinism
+/// is unit-testable.
+#[allow(clippy::too_many_arguments)]
+fn compute_salt(
+    id: U256,
+    creator: Address,
+    nft_contract: Address,
+    token_id: U256,
+    reserve_price: U256,
+    commit_duration: U256,
+    reveal_duration: U256,
+    min_deposit: U256,
+    buyout_price: U256,
+    oracle: Address,
+) -> B256 {
+    let mut salt_preimage = Vec::new();
+    salt_preimage.extend_from_slice(&id.as_le_bytes());
+    salt_preimage.extend_from_slice(creator.as_slice());
+    salt_preimage.extend_from_slice(nft_contract.as_slice());
+    salt_preimage.extend_from_slice(&token_id.as_le_bytes());
+    salt_preimage.extend_from_slice(&reserve_price.as_le_bytes());
+    salt_preimage.extend_from_slice(&commit_duration.as_le_bytes());
+    salt_preimage.extend_from_slice(&reveal_duration.as_le_bytes());
+    salt_preimage.extend_from_slice(&min_deposit.as_le_bytes());
+    salt_preimage.extend_from_slice(&buyout_price.as_le_bytes());
+    salt_preimage.extend_from_slice(oracle.as_slice());
+    B256::from_slice(&crypto::keccak(salt_preimage)[0..32])
+}
+
+/// Read an `Address` from the low 20 bytes of a 32-byte ABI word.
+fn word_to_address(word: &[u8]) -> Address {
+    Address::from_slice(&word[12..32])
+}
+
+/// Read a `U256` from a 32-byte big-endian ABI word.
+fn word_to_u256(word: &[u8]) -> U256 {
+    U256::from_be_slice(word)
+}
+
+/// Recover the signer address of a 65-byte `(r, s, v)` signature over `digest`
+/// using the ecrecover precompile.
+fn recover_signer(digest: B256, sig: &[u8]) -> Result<Address, Vec<u8>> {
+    if sig.len() != 65 {
+        return Err("Bad signature length".as_bytes().to_vec());
+    }
+    // ecrecover precompile input: hash(32) || v(32) || r(32) || s(32)
+    let mut input = [0u8; 128];
+    input[0..32].copy_from_slice(digest.as_slice());
+    input[63] = sig[64]; // v, right-aligned in its word
+    input[64..96].copy_from_slice(&sig[0..32]); // r
+    input[96..128].copy_from_slice(&sig[32..64]); // s
+
+    let out = unsafe {
+        RawCall::new_static()
+            .call(Address::with_last_byte(1), &input)
+            .map_err(|_| "ecrecover failed".as_bytes().to_vec())?
+    };
+    if out.len() != 32 {
+        return Err("ecrecover failed".as_bytes().to_vec());
+    }
+    Ok(Address::from_slice(&out[12..32]))
+}
+
 impl SealedBidAuctionFactory {
     fn only_owner(&self) -> Result<(), Vec<u8>> {
         if msg::sender() != self.owner.get() {
@@ -182,4 +470,177 @@ impl SealedBidAuctionFactory {
         }
         Ok(())
     }
+
+    /// Validate parameters, deploy one instance via CREATE2, and record it in the
+    /// registry. Returns the assigned id and the deployed address. Shared by the
+    /// single-auction, edition-series and cross-chain entry points so book-keeping
+    /// stays in one place.
+    fn deploy_auction<D: Deployer>(
+        &mut self,
+        deployer: &D,
+        nft_contract: Address,
+        token_id: U256,
+        reserve_price: U256,
+        commit_duration: U256,
+        reveal_duration: U256,
+        min_deposit: U256,
+        buyout_price: U256,
+        oracle: Address,
+    ) -> Result<(U256, Address), Vec<u8>> {
+        if self.paused.get() {
+            return Err("Factory is paused".as_bytes().to_vec());
+        }
+
+        if nft_contract == Address::ZERO {
+            return Err("Invalid NFT contract".as_bytes().to_vec());
+        }
+        if reveal_duration == U256::ZERO {
+            return Err("Reveal duration must be > 0".as_bytes().to_vec());
+        }
+        if commit_duration == U256::ZERO {
+            return Err("Commit duration must be > 0".as_bytes().to_vec());
+        }
+        if min_deposit == U256::ZERO {
+            return Err("Min deposit must be > 0".as_bytes().to_vec());
+        }
+        // A buyout of 0 disables the instant-sale path; otherwise it must sit at
+        // or above the reserve so the guaranteed sale never undercuts it.
+        if buyout_price != U256::ZERO && buyout_price < reserve_price {
+            return Err("Buyout below reserve".as_bytes().to_vec());
+        }
+
+        let next_id = self.auction_count.get() + U256::from(1u8);
+        let creator = msg::sender();
+
+        // Deterministic salt: binds instance to creator + asset + timing + id +
+        // buyout + oracle.
+        let salt = compute_salt(
+            next_id,
+            creator,
+            nft_contract,
+            token_id,
+            reserve_price,
+            commit_duration,
+            reveal_duration,
+            min_deposit,
+            buyout_price,
+            oracle,
+        );
+
+        // Deploy instance through the injected deployer (CREATE2 in production).
+        let deployed = deployer.deploy(salt, SEALED_BID_AUCTION_WASM, U256::ZERO)?;
+
+        // Book-keeping
+        self.auctions.setter(next_id).set(deployed);
+        self.creators.setter(next_id).set(creator);
+        self.buyout_enabled.setter(next_id).set(buyout_price != U256::ZERO);
+        self.oracle_of.setter(next_id).set(oracle);
+        self.nft_to_auctions.setter(nft_contract).push(next_id);
+        self.auction_count.set(next_id);
+
+        evm::log(AuctionCreated {
+            id: next_id,
+            creator,
+            instance: deployed,
+            nftContract: nft_contract,
+            tokenId: token_id,
+        });
+
+        Ok((next_id, deployed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::testing::*;
+
+    /// Deployer stub that records nothing and hands back a fixed instance address.
+    struct MockDeployer;
+
+    impl Deployer for MockDeployer {
+        fn deploy(&self, _salt: B256, _code: &[u8], _value: U256) -> Result<Address, Vec<u8>> {
+            Ok(Address::with_last_byte(0xbe))
+        }
+    }
+
+    fn params() -> (Address, U256, U256, U256, U256, U256, U256, Address) {
+        (
+            Address::with_last_byte(0xaa), // nft
+            U256::from(7u8),               // token id
+            U256::from(100u8),             // reserve
+            U256::from(600u16),            // commit
+            U256::from(600u16),            // reveal
+            U256::from(1u8),               // min deposit
+            U256::ZERO,                    // buyout disabled
+            Address::ZERO,                 // no oracle
+        )
+    }
+
+    #[test]
+    fn salt_is_deterministic() {
+        let (nft, tid, res, c, r, md, bo, oracle) = params();
+        let creator = Address::with_last_byte(0x11);
+        let a = compute_salt(U256::from(1u8), creator, nft, tid, res, c, r, md, bo, oracle);
+        let b = compute_salt(U256::from(1u8), creator, nft, tid, res, c, r, md, bo, oracle);
+        assert_eq!(a, b);
+        // A different id must yield a different salt.
+        let c2 = compute_salt(U256::from(2u8), creator, nft, tid, res, c, r, md, bo, oracle);
+        assert_ne!(a, c2);
+    }
+
+    #[test]
+    fn id_increments_per_deploy() {
+        let vm = TestVM::default();
+        let mut factory = SealedBidAuctionFactory::from(&vm);
+        factory.new().unwrap();
+
+        let (nft, tid, res, c, r, md, bo, oracle) = params();
+        let (id1, addr1) = factory
+            .deploy_auction(&MockDeployer, nft, tid, res, c, r, md, bo, oracle)
+            .unwrap();
+        let (id2, _) = factory
+            .deploy_auction(&MockDeployer, nft, tid, res, c, r, md, bo, oracle)
+            .unwrap();
+
+        assert_eq!(id1, U256::from(1u8));
+        assert_eq!(id2, U256::from(2u8));
+        assert_eq!(factory.get_auction_count(), U256::from(2u8));
+        assert_eq!(factory.get_auction(id1), addr1);
+    }
+
+    #[test]
+    fn pause_gates_deploys() {
+        let vm = TestVM::default();
+        let mut factory = SealedBidAuctionFactory::from(&vm);
+        factory.new().unwrap();
+        factory.pause().unwrap();
+
+        let (nft, tid, res, c, r, md, bo, oracle) = params();
+        let err = factory
+            .deploy_auction(&MockDeployer, nft, tid, res, c, r, md, bo, oracle)
+            .unwrap_err();
+        assert_eq!(err, b"Factory is paused".to_vec());
+    }
+
+    #[test]
+    fn get_auctions_handles_out_of_range_offsets() {
+        let vm = TestVM::default();
+        let mut factory = SealedBidAuctionFactory::from(&vm);
+        factory.new().unwrap();
+
+        let (nft, tid, res, c, r, md, bo, oracle) = params();
+        factory
+            .deploy_auction(&MockDeployer, nft, tid, res, c, r, md, bo, oracle)
+            .unwrap();
+
+        // Offset at the top of the range must not overflow on `offset + 1`.
+        assert!(factory
+            .get_auctions(U256::MAX, U256::from(10u8))
+            .is_empty());
+        // Offset at or beyond the count yields nothing.
+        assert!(factory
+            .get_auctions(factory.get_auction_count(), U256::from(10u8))
+            .is_empty());
+    }
 }